@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use crate::api_call::api_call;
 use crate::endpoints::{horizon::Record, CallBuilder, Server};
+use crate::error::StellarError;
 use crate::types::Operation;
 use crate::utils::{Direction, Endpoint};
 
@@ -52,7 +53,7 @@ impl<'a> CallBuilder<Operation> for PaymentCallBuilder<'a> {
         self
     }
 
-    fn call(&self) -> Result<Record<Operation>, anyhow::Error> {
+    fn call(&self) -> Result<Record<Operation>, StellarError> {
         let url = format!(
             "{}{}{}",
             &self.server_url,
@@ -66,6 +67,7 @@ impl<'a> CallBuilder<Operation> for PaymentCallBuilder<'a> {
             &self.query_params,
             self.token,
         )
+        .map_err(|e| StellarError::Transport(e.to_string()))
     }
 }
 
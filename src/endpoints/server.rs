@@ -1,14 +1,26 @@
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
 use serde_json;
 
 use crate::endpoints::{
     fee_stats::FeeStats, ledger_call_builder::LedgerCallBuilder, Account, AccountCallBuilder,
     AssetCallBuilder, Ledger, Offer, Transaction, TransactionCallBuilder,
 };
-use crate::utils::{req, Endpoint};
+use crate::error::StellarError;
+use crate::signer::Signer;
+use crate::transaction::TransactionEnvelope;
+use crate::utils::{post, req, Endpoint};
 
 use super::OfferCallBuilder;
 
-#[derive(Debug)]
+/// Horizon's `/transactions` resource, returned both by `GET
+/// /transactions/{hash}` and by a successful `POST /transactions`.
+pub type TransactionResponse = Transaction;
+
+#[derive(Debug, Clone)]
 pub struct Server(pub String);
 
 impl Server {
@@ -16,6 +28,19 @@ impl Server {
         Server(network_id)
     }
 
+    /// Co-sign `envelope` with `signer` before submitting it.
+    ///
+    /// Takes `&dyn Signer` so a `Keypair` and a `LedgerSigner` are
+    /// interchangeable here: the envelope doesn't need to know whether the
+    /// secret key lives in memory or on a hardware wallet.
+    pub fn sign_transaction(
+        &self,
+        envelope: &mut TransactionEnvelope,
+        signer: &dyn Signer,
+    ) -> Result<(), StellarError> {
+        envelope.sign(signer).map_err(StellarError::from)
+    }
+
     pub fn accounts(&self) -> AccountCallBuilder {
         AccountCallBuilder {
             server: self,
@@ -42,22 +67,18 @@ impl Server {
         }
     }
 
-    pub fn load_account(&self, account_id: &str) -> Result<Account, &str> {
+    pub fn load_account(&self, account_id: &str) -> Result<Account, StellarError> {
         let url = format!("{}/accounts/{}", self.0, account_id);
-        let resp = req(&url).unwrap();
-
-        let parsed: Account = serde_json::from_str(&resp).unwrap();
+        let resp = req(&url).map_err(|e| StellarError::Transport(e.to_string()))?;
 
-        Ok(parsed)
+        serde_json::from_str(&resp).map_err(|e| StellarError::from_response_body(&resp, e))
     }
 
-    pub fn load_transaction(&self, hash: &str) -> Result<Transaction, &str> {
+    pub fn load_transaction(&self, hash: &str) -> Result<Transaction, StellarError> {
         let url = format!("{}/transactions/{}", self.0, hash);
-        let resp = req(&url).unwrap();
+        let resp = req(&url).map_err(|e| StellarError::Transport(e.to_string()))?;
 
-        let parsed: Transaction = serde_json::from_str(&resp).unwrap();
-
-        Ok(parsed)
+        serde_json::from_str(&resp).map_err(|e| StellarError::from_response_body(&resp, e))
     }
 
     pub fn transactions(&self) -> TransactionCallBuilder {
@@ -71,13 +92,11 @@ impl Server {
         }
     }
 
-    pub fn load_ledger(&self, sequence: u64) -> Result<Ledger, &str> {
+    pub fn load_ledger(&self, sequence: u64) -> Result<Ledger, StellarError> {
         let url = format!("{}/ledgers/{}", self.0, sequence);
-        let resp = req(&url).unwrap();
-
-        let parsed: Ledger = serde_json::from_str(&resp).unwrap();
+        let resp = req(&url).map_err(|e| StellarError::Transport(e.to_string()))?;
 
-        Ok(parsed)
+        serde_json::from_str(&resp).map_err(|e| StellarError::from_response_body(&resp, e))
     }
 
     pub fn ledgers(&self) -> LedgerCallBuilder {
@@ -90,13 +109,11 @@ impl Server {
         }
     }
 
-    pub fn load_offer(&self, offer_id: &str) -> Result<Offer, &str> {
+    pub fn load_offer(&self, offer_id: &str) -> Result<Offer, StellarError> {
         let url = format!("{}/offers/{}", self.0, offer_id);
-        let resp = req(&url).unwrap();
-
-        let parsed: Offer = serde_json::from_str(&resp).unwrap();
+        let resp = req(&url).map_err(|e| StellarError::Transport(e.to_string()))?;
 
-        Ok(parsed)
+        serde_json::from_str(&resp).map_err(|e| StellarError::from_response_body(&resp, e))
     }
 
     pub fn offers(&self) -> OfferCallBuilder {
@@ -113,13 +130,93 @@ impl Server {
         }
     }
 
-    pub fn fee_stats(&self) -> Result<FeeStats, &str> {
+    pub fn fee_stats(&self) -> Result<FeeStats, StellarError> {
         let url = format!("{}/fee_stats", self.0);
-        let resp = req(&url).unwrap();
+        let resp = req(&url).map_err(|e| StellarError::Transport(e.to_string()))?;
+
+        serde_json::from_str(&resp).map_err(|e| StellarError::from_response_body(&resp, e))
+    }
 
-        let parsed: FeeStats = serde_json::from_str(&resp).unwrap();
+    /// Submit a signed transaction envelope (base64 XDR) to Horizon. On
+    /// failure, Horizon's `problem+json` body is decoded into
+    /// [`StellarError::Horizon`], whose `extras.result_codes` tells apart
+    /// e.g. `tx_bad_seq`/`tx_insufficient_fee` from per-operation codes
+    /// like `op_underfunded`.
+    pub fn submit_transaction(
+        &self,
+        envelope_xdr: &str,
+    ) -> Result<TransactionResponse, StellarError> {
+        let url = format!("{}/transactions", self.0);
+
+        let mut form = HashMap::new();
+        form.insert(String::from("tx"), envelope_xdr.to_string());
+
+        let resp = post(&url, &form).map_err(|e| StellarError::Transport(e.to_string()))?;
+
+        serde_json::from_str(&resp).map_err(|e| StellarError::from_response_body(&resp, e))
+    }
+
+    /// Submit a transaction under a `submit_timeout` deadline, and if the
+    /// deadline genuinely elapses without a response, fall back to polling
+    /// `GET /transactions/{hash}` until the transaction is found or
+    /// `max_attempts` is exhausted.
+    ///
+    /// The submission runs on a worker thread so the deadline is enforced by
+    /// this method itself via `recv_timeout`, rather than by guessing from
+    /// the wording of whatever error `post` happened to return: an elapsed
+    /// deadline is the only thing that falls back to polling. A response
+    /// that arrives before the deadline — success or a genuine
+    /// [`StellarError::Transport`]/[`StellarError::Horizon`] failure — is
+    /// returned as-is, since in the latter case the request never reached
+    /// Horizon (or was rejected outright) and polling by hash would just
+    /// busy-wait on the same underlying failure.
+    pub fn submit_transaction_async(
+        &self,
+        envelope_xdr: &str,
+        hash: &str,
+        submit_timeout: Duration,
+        poll_interval: Duration,
+        max_attempts: u32,
+    ) -> Result<TransactionResponse, StellarError> {
+        let (tx, rx) = mpsc::channel();
+        let server = self.clone();
+        let envelope_xdr = envelope_xdr.to_string();
+
+        thread::spawn(move || {
+            let _ = tx.send(server.submit_transaction(&envelope_xdr));
+        });
+
+        match rx.recv_timeout(submit_timeout) {
+            Ok(result) => result,
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                self.poll_for_transaction(hash, poll_interval, max_attempts)
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => Err(StellarError::Transport(
+                String::from("submission worker exited without a response"),
+            )),
+        }
+    }
+
+    fn poll_for_transaction(
+        &self,
+        hash: &str,
+        poll_interval: Duration,
+        max_attempts: u32,
+    ) -> Result<TransactionResponse, StellarError> {
+        for _ in 0..max_attempts {
+            std::thread::sleep(poll_interval);
+
+            match self.load_transaction(hash) {
+                Ok(tx) => return Ok(tx),
+                Err(StellarError::Horizon(problem)) if problem.status == 404 => continue,
+                Err(e) => return Err(e),
+            }
+        }
 
-        Ok(parsed)
+        Err(StellarError::Timeout(format!(
+            "transaction {} not found after {} attempts",
+            hash, max_attempts
+        )))
     }
 }
 
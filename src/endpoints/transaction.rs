@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::api_call::api_call;
+use crate::endpoints::{horizon::Record, CallBuilder, Server};
+use crate::error::StellarError;
+use crate::utils::{Direction, Endpoint};
+
+/// Horizon's `/transactions` resource.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Transaction {
+    pub id: String,
+    pub paging_token: String,
+    pub successful: bool,
+    pub hash: String,
+    pub ledger: u64,
+    pub created_at: String,
+    pub source_account: String,
+    pub source_account_sequence: String,
+    pub fee_account: String,
+    pub fee_charged: String,
+    pub max_fee: String,
+    pub operation_count: u32,
+    pub envelope_xdr: String,
+    pub result_xdr: String,
+    pub result_meta_xdr: String,
+    pub fee_meta_xdr: String,
+    pub memo_type: String,
+    pub memo: Option<String>,
+    #[serde(default)]
+    pub signatures: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct TransactionCallBuilder<'a> {
+    pub(crate) server: &'a Server,
+    pub(crate) cursor: Option<String>,
+    pub(crate) order: Option<Direction>,
+    pub(crate) limit: Option<u8>,
+    pub(crate) include_failed: bool,
+    pub(crate) endpoint: Endpoint,
+}
+
+impl<'a> TransactionCallBuilder<'a> {
+    /// Whether to include failed transactions in the result set.
+    pub fn include_failed(&mut self, include_failed: bool) -> &mut Self {
+        self.include_failed = include_failed;
+        self
+    }
+}
+
+impl<'a> CallBuilder<Transaction> for TransactionCallBuilder<'a> {
+    fn cursor(&mut self, cursor: &str) -> &mut Self {
+        self.cursor = Some(cursor.to_string());
+        self
+    }
+
+    fn order(&mut self, dir: Direction) -> &mut Self {
+        self.order = Some(dir);
+        self
+    }
+
+    fn limit(&mut self, limit: u8) -> &mut Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    fn for_endpoint(&mut self, endpoint: Endpoint) -> &mut Self {
+        self.endpoint = endpoint;
+        self
+    }
+
+    fn call(&self) -> Result<Record<Transaction>, StellarError> {
+        let mut query_params = HashMap::new();
+
+        if let Some(cursor) = &self.cursor {
+            query_params.insert(String::from("cursor"), cursor.clone());
+        }
+        if let Some(order) = &self.order {
+            query_params.insert(String::from("order"), order.to_string());
+        }
+        if let Some(limit) = self.limit {
+            query_params.insert(String::from("limit"), limit.to_string());
+        }
+        query_params.insert(
+            String::from("include_failed"),
+            self.include_failed.to_string(),
+        );
+
+        let url = format!(
+            "{}{}{}",
+            &self.server.0,
+            self.endpoint.as_str(),
+            "/transactions",
+        );
+
+        api_call::<Record<Transaction>>(url, crate::types::HttpMethod::GET, &query_params, &None)
+            .map_err(|e| StellarError::Transport(e.to_string()))
+    }
+}
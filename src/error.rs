@@ -0,0 +1,115 @@
+use serde::Deserialize;
+use thiserror::Error;
+
+/// Crate-wide error type returned by [`crate::endpoints::Server`] and the
+/// `CallBuilder` implementations, replacing the `unwrap`-based paths that
+/// used to panic on network hiccups or unexpected Horizon payloads.
+#[derive(Error, Debug)]
+pub enum StellarError {
+    #[error("request to Horizon failed: {0}")]
+    Transport(String),
+
+    #[error("request to Horizon timed out: {0}")]
+    Timeout(String),
+
+    #[error("failed to decode Horizon response as JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("invalid StrKey-encoded value: {0}")]
+    StrKey(#[from] anyhow::Error),
+
+    #[error("Horizon returned an error: {0}")]
+    Horizon(HorizonProblem),
+}
+
+impl StellarError {
+    /// Turn a Horizon response body that failed to parse as the expected
+    /// resource into a [`StellarError`]: a `problem+json` body becomes
+    /// [`StellarError::Horizon`], anything else keeps the original JSON error.
+    pub fn from_response_body(body: &str, json_err: serde_json::Error) -> Self {
+        match serde_json::from_str::<HorizonProblem>(body) {
+            Ok(problem) => StellarError::Horizon(problem),
+            Err(_) => StellarError::Json(json_err),
+        }
+    }
+}
+
+/// Horizon's RFC-7807 `problem+json` error body, e.g. returned on a failed
+/// `submit_transaction` call.
+#[derive(Debug, Clone, Deserialize)]
+pub struct HorizonProblem {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: Option<String>,
+    #[serde(default)]
+    pub extras: HorizonProblemExtras,
+}
+
+impl std::fmt::Display for HorizonProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({}): {}", self.title, self.status, self.problem_type)
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct HorizonProblemExtras {
+    pub result_codes: Option<TransactionResultCodes>,
+}
+
+/// The `extras.result_codes` object on a failed transaction submission,
+/// distinguishing e.g. `tx_bad_seq`/`tx_insufficient_fee` from the
+/// per-operation codes like `op_underfunded`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TransactionResultCodes {
+    pub transaction: String,
+    #[serde(default)]
+    pub operations: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_response_body_decodes_horizon_problem_with_result_codes() {
+        let body = r#"{
+            "type": "https://stellar.org/horizon-errors/transaction_failed",
+            "title": "Transaction Failed",
+            "status": 400,
+            "detail": "The transaction failed when submitted to the stellar network.",
+            "extras": {
+                "result_codes": {
+                    "transaction": "tx_failed",
+                    "operations": ["op_success", "op_underfunded"]
+                }
+            }
+        }"#;
+
+        let json_err = serde_json::from_str::<u8>("not json").unwrap_err();
+        let err = StellarError::from_response_body(body, json_err);
+
+        match err {
+            StellarError::Horizon(problem) => {
+                assert_eq!(problem.status, 400);
+                assert_eq!(problem.title, "Transaction Failed");
+
+                let codes = problem.extras.result_codes.unwrap();
+                assert_eq!(codes.transaction, "tx_failed");
+                assert_eq!(codes.operations, vec!["op_success", "op_underfunded"]);
+            }
+            other => panic!("expected StellarError::Horizon, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_from_response_body_falls_back_to_json_error_for_non_problem_body() {
+        let body = "not a problem document";
+        let json_err = serde_json::from_str::<u8>(body).unwrap_err();
+
+        let err = StellarError::from_response_body(body, json_err);
+
+        assert!(matches!(err, StellarError::Json(_)));
+    }
+}
@@ -1,11 +1,73 @@
 use anyhow::bail;
+use hmac::{Hmac, Mac};
 use nacl::sign::{generate_keypair, signature, verify};
+use pbkdf2::pbkdf2;
 use str_key::StrKey;
 
+use crate::signer::Signer;
 use crate::str_key;
+use crate::transaction::DecoratedSignature;
 use ed25519_dalek::{ExpandedSecretKey, SecretKey, Sha512};
 use ed25519_dalek::Digest;
 
+type HmacSha512 = Hmac<sha2::Sha512>;
+
+/// SEP-0005 account derivation path: `m/44'/148'/account_index'`. ed25519
+/// has no unhardened derivation, so every level is hardened regardless of
+/// the high bit being set explicitly here.
+const PURPOSE: u32 = 44;
+const COIN_TYPE: u32 = 148;
+
+/// SLIP-0010 master node for ed25519: `HMAC-SHA512("ed25519 seed", seed)`,
+/// split into the 32-byte private key `IL` and 32-byte chain code `IR`.
+fn slip10_master_node(seed: &[u8]) -> ([u8; 32], [u8; 32]) {
+    let mut mac =
+        HmacSha512::new_from_slice(b"ed25519 seed").expect("HMAC accepts a key of any size");
+    mac.update(seed);
+    let result = mac.finalize().into_bytes();
+
+    let mut il = [0u8; 32];
+    let mut ir = [0u8; 32];
+    il.copy_from_slice(&result[..32]);
+    ir.copy_from_slice(&result[32..]);
+    (il, ir)
+}
+
+/// One step of SLIP-0010 hardened ed25519 child derivation:
+/// `HMAC-SHA512(IR, 0x00 || IL || ser32(index | 0x80000000))`.
+fn slip10_derive_child(il: [u8; 32], ir: [u8; 32], index: u32) -> ([u8; 32], [u8; 32]) {
+    let hardened_index = index | 0x8000_0000;
+
+    let mut data = Vec::with_capacity(1 + 32 + 4);
+    data.push(0x00);
+    data.extend_from_slice(&il);
+    data.extend_from_slice(&hardened_index.to_be_bytes());
+
+    let mut mac = HmacSha512::new_from_slice(&ir).expect("HMAC accepts a key of any size");
+    mac.update(&data);
+    let result = mac.finalize().into_bytes();
+
+    let mut next_il = [0u8; 32];
+    let mut next_ir = [0u8; 32];
+    next_il.copy_from_slice(&result[..32]);
+    next_ir.copy_from_slice(&result[32..]);
+    (next_il, next_ir)
+}
+
+/// Derive the ed25519 seed for SEP-0005 path `m/44'/148'/account_index'`
+/// from a BIP-39/SLIP-10 seed.
+fn slip10_derive_seed(seed: &[u8], account_index: u32) -> [u8; 32] {
+    let (mut il, mut ir) = slip10_master_node(seed);
+
+    for index in [PURPOSE, COIN_TYPE, account_index] {
+        let (next_il, next_ir) = slip10_derive_child(il, ir, index);
+        il = next_il;
+        ir = next_ir;
+    }
+
+    il
+}
+
 
 #[derive(Debug, Clone)]
 pub struct Keypair {
@@ -114,6 +176,33 @@ impl Keypair {
         Self::new_from_secret_key_with_nonce(seed.to_vec(),nonce.to_vec())
     }
 
+    /// Derive a keypair from a raw BIP-39/SLIP-10 seed along the SEP-0005
+    /// path `m/44'/148'/account_index'`, the derivation every other Stellar
+    /// wallet uses; the ad-hoc nonce constructors above remain available.
+    pub fn from_slip10_seed(seed: &[u8], account_index: u32) -> Result<Self, anyhow::Error> {
+        let derived_seed = slip10_derive_seed(seed, account_index);
+
+        Keypair::from_raw_ed25519_seed(&derived_seed)
+    }
+
+    /// Derive a keypair from a BIP-39 mnemonic phrase, per SEP-0005: the
+    /// mnemonic is stretched into a 64-byte seed with PBKDF2-HMAC-SHA512
+    /// (2048 rounds, salt `"mnemonic" + passphrase`), then derived along
+    /// `m/44'/148'/account_index'` with SLIP-0010.
+    pub fn from_mnemonic(
+        phrase: &str,
+        passphrase: &str,
+        account_index: u32,
+    ) -> Result<Self, anyhow::Error> {
+        let salt = format!("mnemonic{}", passphrase);
+
+        let mut seed = [0u8; 64];
+        pbkdf2::<HmacSha512>(phrase.as_bytes(), salt.as_bytes(), 2048, &mut seed)
+            .map_err(|e| anyhow::anyhow!("failed to stretch mnemonic into a seed: {e}"))?;
+
+        Keypair::from_slip10_seed(&seed, account_index)
+    }
+
 
     pub fn raw_secret_key(&self) -> Option<Vec<u8>> {
         self.secret_seed.clone()
@@ -161,15 +250,151 @@ impl Keypair {
         Self::new_from_secret_key(rand::random::<[u8; 32]>().to_vec())
     }
 
+    /// Base32 alphabet (RFC 4648) a StrKey public key is encoded with; any
+    /// character outside this set can never appear after the leading `G`.
+    const VANITY_ALPHABET: &'static str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+    /// Characters that can follow the leading `G`: that position carries the
+    /// version byte's low 3 bits (all zero) and the key's top 2 bits.
+    const VANITY_SECOND_CHARS: &'static str = "ABCD";
+
+    /// Expected number of random keypairs [`Keypair::find_vanity`] will have
+    /// to try, on average, to find one matching `prefix`: 4 choices for the
+    /// first character and 32 for each one after it. A prefix that can never
+    /// match gives `u64::MAX`. Callers can use this to report search
+    /// progress; the search itself doesn't print anything.
+    pub fn vanity_expected_attempts(prefix: &str) -> u64 {
+        let prefix = prefix.to_uppercase();
+        if !prefix.chars().all(|c| Self::VANITY_ALPHABET.contains(c)) {
+            return u64::MAX;
+        }
+        match prefix.chars().next() {
+            None => 1,
+            Some(c) if Self::VANITY_SECOND_CHARS.contains(c) => {
+                4u64.saturating_mul(32u64.saturating_pow(prefix.len() as u32 - 1))
+            }
+            Some(_) => u64::MAX,
+        }
+    }
+
+    /// Generate random keypairs across `threads` worker threads until one's
+    /// StrKey-encoded public key starts with `G` followed by `prefix`,
+    /// returning the first match found.
+    pub fn find_vanity(prefix: &str, threads: usize) -> Result<Self, anyhow::Error> {
+        Self::find_vanity_cancellable(prefix, threads, std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)))
+    }
 
+    /// Same as [`Keypair::find_vanity`], but takes a shared cancellation
+    /// flag the caller can set from another thread to abort the search early.
+    pub fn find_vanity_cancellable(
+        prefix: &str,
+        threads: usize,
+        cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    ) -> Result<Self, anyhow::Error> {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::{Arc, Mutex};
+
+        let prefix = prefix.to_uppercase();
+
+        if !prefix.chars().all(|c| Self::VANITY_ALPHABET.contains(c))
+            || prefix
+                .chars()
+                .next()
+                .map_or(false, |c| !Self::VANITY_SECOND_CHARS.contains(c))
+        {
+            bail!(
+                "prefix '{}' can never follow 'G' in a StrKey public key",
+                prefix
+            );
+        }
+
+        let found = Arc::new(AtomicBool::new(false));
+        let result: Arc<Mutex<Option<Keypair>>> = Arc::new(Mutex::new(None));
+
+        std::thread::scope(|scope| {
+            for _ in 0..threads.max(1) {
+                let found = Arc::clone(&found);
+                let result = Arc::clone(&result);
+                let cancel = Arc::clone(&cancel);
+                let prefix = prefix.clone();
+
+                scope.spawn(move || {
+                    while !found.load(Ordering::Relaxed) && !cancel.load(Ordering::Relaxed) {
+                        if let Ok(candidate) = Keypair::random() {
+                            if candidate.public_key()[1..].starts_with(&prefix) {
+                                *result.lock().unwrap() = Some(candidate);
+                                found.store(true, Ordering::Relaxed);
+                                return;
+                            }
+                        }
+                    }
+                });
+            }
+        });
+
+        let match_result = result
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("vanity search cancelled before a match was found"));
+        match_result
+    }
+
+
+
+    /// The last 4 bytes of the raw public key, used to identify which
+    /// signer a `DecoratedSignature` belongs to without shipping the whole key.
+    pub fn signature_hint(&self) -> [u8; 4] {
+        let mut hint = [0u8; 4];
+        hint.copy_from_slice(&self.public_key[28..32]);
+        hint
+    }
+
+    /// Sign `data` and wrap the result with this keypair's signature hint.
+    pub fn sign_decorated(&self, data: &[u8]) -> Result<DecoratedSignature, anyhow::Error> {
+        Ok(DecoratedSignature {
+            hint: self.signature_hint(),
+            signature: self.sign(data)?,
+        })
+    }
+
+    /// Sign `payload` as a signed-payload signer (CAP-0040): the hint is
+    /// the key's signature hint XORed with the last 4 bytes of the payload
+    /// (or fewer, if the payload is shorter than 4 bytes).
+    pub fn sign_payload_decorated(
+        &self,
+        payload: &[u8],
+    ) -> Result<DecoratedSignature, anyhow::Error> {
+        let mut hint = self.signature_hint();
+        let tail_len = payload.len().min(4);
+        for (i, byte) in payload[payload.len() - tail_len..].iter().enumerate() {
+            hint[4 - tail_len + i] ^= byte;
+        }
+
+        Ok(DecoratedSignature {
+            hint,
+            signature: self.sign(payload)?,
+        })
+    }
 
     // fn master
     // fn xdr_account_id
     // fn xdr_public_key
     // fn xdr_muxed_account
-    // fn signature_hint
-    // fn sign_payload_decorated
-    // fn sign_decorated
+}
+
+impl Signer for Keypair {
+    fn public_key(&self) -> String {
+        Keypair::public_key(self)
+    }
+
+    fn sign(&self, data: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        Keypair::sign(self, data)
+    }
+
+    fn can_sign(&self) -> bool {
+        Keypair::can_sign(self)
+    }
 }
 
 #[cfg(test)]
@@ -292,6 +517,92 @@ mod tests {
         assert!(keypair.verify(&signed_message, &unsigned_message))
     }
 
+    #[test]
+    fn test_from_mnemonic_sep0005_vectors() {
+        let phrase = "illness spike retreat truth genius clock brain pass fit cave bargain toe";
+
+        let expected = [
+            (
+                "GDRXE2BQUC3AZNPVFSCEZ76NJ3WWL25FYFK6RGZGIEKWE4SOOHSUJUJ6",
+                "SBGWSG6BTNCKCOB3DIFBGCVMUPQFYPA2G4O34RMTB343OYPXU5DJDVMN",
+            ),
+            (
+                "GBAW5XGWORWVFE2XTJYDTLDHXTY2Q2MO73HYCGB3XMFMQ562Q2W2GJQX",
+                "SCEPFFWGAG5P2VX5DHIYK3XEMZYLTYWIPWYEKXFHSK25RVMIUNJ7CTIS",
+            ),
+            (
+                "GAY5PRAHJ2HIYBYCLZXTHID6SPVELOOYH2LBPH3LD4RUMXUW3DOYTLXW",
+                "SDAILLEZCSA67DUEP3XUPZJ7NYG7KGVRM46XA7K5QWWUIGADUZCZWTJP",
+            ),
+        ];
+
+        for (i, (public_key, secret_key)) in expected.iter().enumerate() {
+            let mut keypair = Keypair::from_mnemonic(phrase, "", i as u32).unwrap();
+
+            assert_eq!(*public_key, keypair.public_key());
+            assert_eq!(*secret_key, keypair.secret_key().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_sign_decorated() {
+        let seed = String::from("SAZ443I6BNR2MD3G27C4EZIEEFMKOPT4SR6IHZDLXPODEHR2GRQVIC7R");
+        let keypair = Keypair::from_secret_key(&seed).unwrap();
+        let data = "Hello World".as_bytes();
+
+        let decorated = keypair.sign_decorated(data).unwrap();
+
+        assert_eq!(decorated.hint, keypair.signature_hint());
+        assert_eq!(decorated.signature, keypair.sign(data).unwrap());
+        assert!(keypair.verify(data, &decorated.signature));
+    }
+
+    #[test]
+    fn test_sign_payload_decorated() {
+        let seed = String::from("SAZ443I6BNR2MD3G27C4EZIEEFMKOPT4SR6IHZDLXPODEHR2GRQVIC7R");
+        let keypair = Keypair::from_secret_key(&seed).unwrap();
+        let payload = [1u8, 2, 3, 4, 5, 6];
+
+        let decorated = keypair.sign_payload_decorated(&payload).unwrap();
+
+        let mut expected_hint = keypair.signature_hint();
+        for (i, byte) in payload[payload.len() - 4..].iter().enumerate() {
+            expected_hint[i] ^= byte;
+        }
+
+        assert_eq!(decorated.hint, expected_hint);
+        assert_eq!(decorated.signature, keypair.sign(&payload).unwrap());
+    }
+
+    #[test]
+    fn test_sign_payload_decorated_short_payload() {
+        let seed = String::from("SAZ443I6BNR2MD3G27C4EZIEEFMKOPT4SR6IHZDLXPODEHR2GRQVIC7R");
+        let keypair = Keypair::from_secret_key(&seed).unwrap();
+        let payload = [7u8, 8];
+
+        let decorated = keypair.sign_payload_decorated(&payload).unwrap();
+
+        let mut expected_hint = keypair.signature_hint();
+        expected_hint[2] ^= payload[0];
+        expected_hint[3] ^= payload[1];
+
+        assert_eq!(decorated.hint, expected_hint);
+        assert_eq!(decorated.signature, keypair.sign(&payload).unwrap());
+    }
+
+    #[test]
+    fn test_find_vanity() {
+        let keypair = Keypair::find_vanity("A", 2).unwrap();
+
+        assert!(keypair.public_key().starts_with("GA"));
+    }
+
+    #[test]
+    fn test_find_vanity_rejects_impossible_prefix() {
+        assert!(Keypair::find_vanity("0", 1).is_err());
+        assert!(Keypair::find_vanity("a!", 1).is_err());
+    }
+
     #[test]
     fn test_random_keypair() {
         let keypair_1 = Keypair::random().unwrap();
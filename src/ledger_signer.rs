@@ -0,0 +1,142 @@
+#![cfg(feature = "ledger")]
+
+use anyhow::{anyhow, bail, Error};
+use ledger_apdu::APDUCommand;
+use ledger_transport_hid::{hidapi::HidApi, TransportNativeHID};
+
+use crate::signer::Signer;
+use crate::str_key::StrKey;
+
+const CLA: u8 = 0xE0;
+const INS_GET_PUBLIC_KEY: u8 = 0x02;
+const INS_SIGN: u8 = 0x04;
+
+const P1_NON_CONFIRM: u8 = 0x00;
+const P1_CONFIRM: u8 = 0x01;
+const P1_SINGLE: u8 = 0x00;
+const P1_MORE: u8 = 0x80;
+const P2_NONE: u8 = 0x00;
+
+/// Largest data field the Stellar Ledger app accepts per APDU; larger
+/// signing payloads are split across multiple APDUs using the P1 "more" bit.
+const MAX_CHUNK_SIZE: usize = 255;
+
+/// `Signer` backed by a Stellar Ledger app talking over USB-HID.
+///
+/// The secret seed never leaves the device: every [`Signer::sign`] call is
+/// a round trip to the hardware wallet, which prompts the user to confirm
+/// on-screen before returning a signature.
+pub struct LedgerSigner {
+    transport: TransportNativeHID,
+    derivation_path: Vec<u32>,
+    public_key: Vec<u8>,
+}
+
+impl LedgerSigner {
+    /// Connect to the first Ledger device found over USB-HID and fetch the
+    /// public key for `m/44'/148'/account_index'`. When `confirm` is set,
+    /// the user is asked to verify the address on the device screen.
+    pub fn connect(account_index: u32, confirm: bool) -> Result<Self, Error> {
+        let hidapi = HidApi::new().map_err(|e| anyhow!("failed to open HID API: {e}"))?;
+        let transport = TransportNativeHID::new(&hidapi)
+            .map_err(|e| anyhow!("failed to connect to Ledger device: {e}"))?;
+
+        let derivation_path = vec![44, 148, account_index];
+        let public_key = Self::request_public_key(&transport, &derivation_path, confirm)?;
+
+        Ok(Self {
+            transport,
+            derivation_path,
+            public_key,
+        })
+    }
+
+    /// Serialize a BIP-44 derivation path as a length-prefixed list of
+    /// big-endian hardened `u32` indices, per the Ledger Stellar app's APDU
+    /// encoding.
+    fn serialize_path(path: &[u32]) -> Vec<u8> {
+        let mut data = vec![path.len() as u8];
+        for index in path {
+            data.extend_from_slice(&(index | 0x8000_0000).to_be_bytes());
+        }
+        data
+    }
+
+    fn request_public_key(
+        transport: &TransportNativeHID,
+        path: &[u32],
+        confirm: bool,
+    ) -> Result<Vec<u8>, Error> {
+        let command = APDUCommand {
+            cla: CLA,
+            ins: INS_GET_PUBLIC_KEY,
+            p1: if confirm { P1_CONFIRM } else { P1_NON_CONFIRM },
+            p2: P2_NONE,
+            data: Self::serialize_path(path),
+        };
+
+        let answer = transport
+            .exchange(&command)
+            .map_err(|e| anyhow!("Ledger get-public-key APDU failed: {e}"))?;
+
+        if answer.data().len() != 32 {
+            bail!(
+                "unexpected public key length from Ledger: {}",
+                answer.data().len()
+            );
+        }
+
+        Ok(answer.data().to_vec())
+    }
+
+    fn request_signature(&self, payload: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut data = Self::serialize_path(&self.derivation_path);
+        data.extend_from_slice(payload);
+
+        let chunks: Vec<&[u8]> = data.chunks(MAX_CHUNK_SIZE).collect();
+        let mut signature = Vec::new();
+
+        for (i, chunk) in chunks.iter().enumerate() {
+            let is_last = i == chunks.len() - 1;
+            let command = APDUCommand {
+                cla: CLA,
+                ins: INS_SIGN,
+                p1: if is_last { P1_SINGLE } else { P1_MORE },
+                p2: P2_NONE,
+                data: chunk.to_vec(),
+            };
+
+            let answer = self
+                .transport
+                .exchange(&command)
+                .map_err(|e| anyhow!("Ledger sign APDU failed: {e}"))?;
+
+            if is_last {
+                signature = answer.data().to_vec();
+            }
+        }
+
+        if signature.len() != 64 {
+            bail!(
+                "unexpected signature length from Ledger: {}",
+                signature.len()
+            );
+        }
+
+        Ok(signature)
+    }
+}
+
+impl Signer for LedgerSigner {
+    fn public_key(&self) -> String {
+        StrKey::encode_ed25519_public_key(&self.public_key)
+    }
+
+    fn sign(&self, data: &[u8]) -> Result<Vec<u8>, Error> {
+        self.request_signature(data)
+    }
+
+    fn can_sign(&self) -> bool {
+        true
+    }
+}
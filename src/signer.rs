@@ -0,0 +1,21 @@
+use anyhow::Error;
+
+/// A source of Stellar signatures: something that holds (or has access to)
+/// the secret key material for an account and can sign over transaction
+/// payloads on its behalf.
+///
+/// `Keypair` implements this directly over an in-memory secret seed.
+/// `LedgerSigner` (behind the `ledger` feature) implements it by asking a
+/// connected Ledger hardware wallet to sign, so the secret seed never has
+/// to enter the process. Anything accepting `&dyn Signer` can treat both
+/// interchangeably.
+pub trait Signer {
+    /// StrKey-encoded (`G...`) public key of the account this signer signs for.
+    fn public_key(&self) -> String;
+
+    /// Sign `data` and return the raw signature bytes.
+    fn sign(&self, data: &[u8]) -> Result<Vec<u8>, Error>;
+
+    /// Whether this signer currently has access to secret key material.
+    fn can_sign(&self) -> bool;
+}
@@ -0,0 +1,167 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use sha2::{Digest, Sha256};
+
+use crate::signer::Signer;
+use crate::str_key::StrKey;
+
+/// XDR `EnvelopeType.ENVELOPE_TYPE_TX` tag, mixed into the transaction
+/// signature base so a signature can never be replayed across envelope kinds.
+const ENVELOPE_TYPE_TX: u32 = 2;
+
+/// A single entry of a transaction envelope's `signatures` array: a 4-byte
+/// hint identifying the signer, plus the raw signature bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecoratedSignature {
+    pub hint: [u8; 4],
+    pub signature: Vec<u8>,
+}
+
+impl DecoratedSignature {
+    /// Pack the hint and length-prefixed signature the way they sit inside
+    /// a transaction envelope's `DecoratedSignature` XDR structure.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(4 + 4 + self.signature.len());
+        out.extend_from_slice(&self.hint);
+        out.extend_from_slice(&(self.signature.len() as u32).to_be_bytes());
+        out.extend_from_slice(&self.signature);
+        out
+    }
+}
+
+/// A transaction being collected for multisig co-signing.
+///
+/// Holds the transaction body XDR and the network passphrase needed to
+/// compute the signature base, and accumulates a [`DecoratedSignature`] per
+/// call to [`TransactionEnvelope::sign`] so a partially-signed transaction
+/// can be handed off between signers holding different keys of a threshold
+/// account.
+#[derive(Debug, Clone)]
+pub struct TransactionEnvelope {
+    network_passphrase: String,
+    tx_body_xdr: Vec<u8>,
+    signatures: Vec<DecoratedSignature>,
+}
+
+impl TransactionEnvelope {
+    pub fn new(network_passphrase: &str, tx_body_xdr: Vec<u8>) -> Self {
+        Self {
+            network_passphrase: network_passphrase.to_string(),
+            tx_body_xdr,
+            signatures: Vec::new(),
+        }
+    }
+
+    /// The signature base every signer signs over:
+    /// `SHA-256(SHA-256(network_passphrase) || envelope_type || tx_body_xdr)`.
+    pub fn signature_base(&self) -> Vec<u8> {
+        let network_id = Sha256::digest(self.network_passphrase.as_bytes());
+
+        let mut payload = Vec::with_capacity(32 + 4 + self.tx_body_xdr.len());
+        payload.extend_from_slice(&network_id);
+        payload.extend_from_slice(&ENVELOPE_TYPE_TX.to_be_bytes());
+        payload.extend_from_slice(&self.tx_body_xdr);
+
+        Sha256::digest(&payload).to_vec()
+    }
+
+    /// Sign the transaction with `signer` and append the resulting
+    /// decorated signature.
+    pub fn sign(&mut self, signer: &dyn Signer) -> Result<(), anyhow::Error> {
+        let base = self.signature_base();
+        let signature = signer.sign(&base)?;
+        let hint = signature_hint_for(&signer.public_key())?;
+
+        self.signatures.push(DecoratedSignature { hint, signature });
+
+        Ok(())
+    }
+
+    pub fn signatures(&self) -> &[DecoratedSignature] {
+        &self.signatures
+    }
+
+    /// Serialize the accumulated signatures as an XDR array: a 4-byte
+    /// big-endian element count followed by each `DecoratedSignature`, so
+    /// the partially-signed envelope can be passed on to the next co-signer.
+    pub fn signatures_bytes(&self) -> Vec<u8> {
+        let mut out = (self.signatures.len() as u32).to_be_bytes().to_vec();
+        out.extend(self.signatures.iter().flat_map(|s| s.to_bytes()));
+        out
+    }
+
+    /// Assemble the transaction body and its accumulated signatures into a
+    /// base64-encoded envelope, ready to hand to
+    /// [`crate::endpoints::Server::submit_transaction`].
+    pub fn to_envelope_xdr(&self) -> String {
+        let mut envelope = self.tx_body_xdr.clone();
+        envelope.extend(self.signatures_bytes());
+
+        BASE64.encode(envelope)
+    }
+}
+
+fn signature_hint_for(public_key: &str) -> Result<[u8; 4], anyhow::Error> {
+    let raw = StrKey::decode_ed25519_public_key(public_key)?;
+
+    let mut hint = [0u8; 4];
+    hint.copy_from_slice(&raw[28..32]);
+    Ok(hint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keypair::Keypair;
+
+    const SEED: &str = "SAZ443I6BNR2MD3G27C4EZIEEFMKOPT4SR6IHZDLXPODEHR2GRQVIC7R";
+    const NETWORK: &str = "Test SDF Network ; September 2015";
+
+    #[test]
+    fn test_signature_base_depends_on_network_and_body() {
+        let a = TransactionEnvelope::new(NETWORK, vec![1, 2, 3]);
+        let b = TransactionEnvelope::new("Public Global Stellar Network ; September 2015", vec![1, 2, 3]);
+        let c = TransactionEnvelope::new(NETWORK, vec![1, 2, 4]);
+
+        assert_ne!(a.signature_base(), b.signature_base());
+        assert_ne!(a.signature_base(), c.signature_base());
+        assert_eq!(a.signature_base(), TransactionEnvelope::new(NETWORK, vec![1, 2, 3]).signature_base());
+    }
+
+    #[test]
+    fn test_sign_appends_matching_decorated_signature() {
+        let keypair = Keypair::from_secret_key(SEED).unwrap();
+        let mut envelope = TransactionEnvelope::new(NETWORK, vec![9, 9, 9]);
+
+        envelope.sign(&keypair).unwrap();
+
+        assert_eq!(envelope.signatures().len(), 1);
+        assert_eq!(envelope.signatures()[0].hint, keypair.signature_hint());
+        assert!(keypair.verify(&envelope.signature_base(), &envelope.signatures()[0].signature));
+    }
+
+    #[test]
+    fn test_signatures_bytes_has_count_prefix() {
+        let keypair = Keypair::from_secret_key(SEED).unwrap();
+        let mut envelope = TransactionEnvelope::new(NETWORK, vec![1]);
+        envelope.sign(&keypair).unwrap();
+
+        let bytes = envelope.signatures_bytes();
+
+        assert_eq!(&bytes[..4], &1u32.to_be_bytes());
+        assert_eq!(bytes[4..8], envelope.signatures()[0].hint);
+    }
+
+    #[test]
+    fn test_to_envelope_xdr_round_trips_body_and_signatures() {
+        let keypair = Keypair::from_secret_key(SEED).unwrap();
+        let tx_body_xdr = vec![5, 6, 7];
+        let mut envelope = TransactionEnvelope::new(NETWORK, tx_body_xdr.clone());
+        envelope.sign(&keypair).unwrap();
+
+        let decoded = BASE64.decode(envelope.to_envelope_xdr()).unwrap();
+
+        assert!(decoded.starts_with(&tx_body_xdr));
+        assert_eq!(decoded[tx_body_xdr.len()..], envelope.signatures_bytes());
+    }
+}